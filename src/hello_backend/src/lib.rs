@@ -1,7 +1,9 @@
 use candid::{CandidType, Decode, Deserialize, Encode};
 use ic_stable_structures::memory_manager::{MemoryId, MemoryManager, VirtualMemory};
-use ic_stable_structures::{storable::Bound, DefaultMemoryImpl, StableBTreeMap, Storable};
-use std::{borrow::Cow, cell::RefCell};
+use ic_stable_structures::{
+    storable::Bound, DefaultMemoryImpl, StableBTreeMap, StableCell, Storable,
+};
+use std::{borrow::Cow, cell::RefCell, collections::BTreeMap};
 
 // Define a struct for Todo items
 #[derive(CandidType, Deserialize, Debug, Clone)]
@@ -10,29 +12,130 @@ struct Todo {
     title: String,
     completed: bool,
 }
+
+// ------------------------------------
+// Errors
+// ------------------------------------
+#[derive(CandidType, Deserialize, Debug, Clone, PartialEq, Eq)]
+enum TodoError {
+    // `first` exceeded the configured `max_page_size`.
+    PageSizeTooLarge { requested: u32, max: u32 },
+    // An update or a batch op referenced an id that does not exist.
+    NotFound { id: u64 },
+    // A title exceeded the configured `max_title_bytes`.
+    TitleTooLong { bytes: usize, max: usize },
+    // A config update requested a value outside the safe range.
+    ConfigOutOfRange { requested: u32, ceiling: u32 },
+}
+
 // ------------------------------------
-// Pagination Trait
+// Pagination
 // ------------------------------------
-trait Paginate {
-    fn get_page(&self, page_num: usize, page_size: usize) -> Vec<(u64, Todo)>;
+// Cursor (keyset) pagination defaults. Keeping the walk keyset-based lets a
+// caller page deep into the list without ever re-walking from the start; the
+// live bounds are read from the settable `Config` below.
+const DEFAULT_PAGE_SIZE: u32 = 20;
+const DEFAULT_MAX_PAGE_SIZE: u32 = 100;
+
+// Opaque cursor plus a hint about whether another page is available.
+#[derive(CandidType, Deserialize, Debug, Clone)]
+struct PageInfo {
+    // The last `id` returned on this page; pass it back as `after` to continue.
+    cursor: Option<u64>,
+    has_next_page: bool,
+}
+
+#[derive(CandidType, Deserialize, Debug, Clone)]
+struct TodosPage {
+    todos: Vec<Todo>,
+    page_info: PageInfo,
 }
 
-// Implement Pagination for BTreeMap<u64, Todo>
-impl Paginate for StableBTreeMap<u64, Todo, Memory> {
-    // impl Paginate for BTreeMap<u64, Todo> {
-    fn get_page(&self, page_num: usize, page_size: usize) -> Vec<(u64, Todo)> {
-        self.iter()
-            .skip((page_num - 1) * page_size)
-            .take(page_size)
-            .collect()
+// Validate a requested page size against the configured bounds, falling back
+// to the default when none is given; an oversized request is rejected.
+fn resolve_page_size(first: Option<u32>) -> Result<usize, TodoError> {
+    let cfg = config();
+    let first = first.unwrap_or(cfg.default_page_size);
+    if first > cfg.max_page_size {
+        return Err(TodoError::PageSizeTooLarge {
+            requested: first,
+            max: cfg.max_page_size,
+        });
     }
+    Ok(first as usize)
 }
+
+// ------------------------------------
+// Runtime configuration
+// ------------------------------------
+// Default title byte budget — comfortably fits realistic titles plus candid
+// framing inside `MAX_VALUE_SIZE`.
+const DEFAULT_MAX_TITLE_BYTES: u32 = 512;
+// Hard ceiling for `max_title_bytes`: beyond this a candid-encoded `Todo` could
+// overrun the declared `Bound` and panic at insert time.
+const MAX_TITLE_BYTES_CEILING: u32 = 900;
+
+// Deployment-tunable knobs, persisted in their own stable memory so they
+// survive upgrades. `max_title_bytes` can be changed at runtime through
+// `set_max_title_bytes`; the page sizes have no setter and keep their defaults.
+#[derive(CandidType, Deserialize, Debug, Clone)]
+struct Config {
+    default_page_size: u32,
+    max_page_size: u32,
+    max_title_bytes: u32,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            default_page_size: DEFAULT_PAGE_SIZE,
+            max_page_size: DEFAULT_MAX_PAGE_SIZE,
+            max_title_bytes: DEFAULT_MAX_TITLE_BYTES,
+        }
+    }
+}
+
+impl Storable for Config {
+    fn to_bytes(&self) -> std::borrow::Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Bounded {
+        max_size: 64,
+        is_fixed_size: false,
+    };
+}
+
+// Snapshot of the current configuration.
+fn config() -> Config {
+    CONFIG.with(|cfg| cfg.borrow().get().clone())
+}
+
 // ------------------------------------
 // storage for todos
 // ------------------------------------
 
 type Memory = VirtualMemory<DefaultMemoryImpl>;
-const MAX_VALUE_SIZE: u32 = 100;
+// Sized to hold a title up to `MAX_TITLE_BYTES_CEILING` plus candid framing for
+// the surrounding `Todo`, so a validated record can never exceed the `Bound`.
+const MAX_VALUE_SIZE: u32 = 1024;
+
+// Reject titles that would overflow the configured byte budget, returning a
+// structured error rather than letting candid encoding panic at insert time.
+fn validate_title(title: &str) -> Result<(), TodoError> {
+    let max = config().max_title_bytes as usize;
+    if title.len() > max {
+        return Err(TodoError::TitleTooLong {
+            bytes: title.len(),
+            max,
+        });
+    }
+    Ok(())
+}
 
 impl Storable for Todo {
     fn to_bytes(&self) -> std::borrow::Cow<[u8]> {
@@ -48,6 +151,59 @@ impl Storable for Todo {
         is_fixed_size: false,
     };
 }
+// ------------------------------------
+// Secondary index keys
+// ------------------------------------
+// The indexes let us answer "all completed todos" / "titles starting with X"
+// with an ordered range scan instead of a full walk of the primary `TODOS`
+// map. Each key is stored like `Todo` itself: candid-encoded with a bounded
+// size. Deriving `Ord` gives lexicographic ordering in field order, so the
+// composite keys sort by `(completed, id)` and `(title, id)` respectively.
+// A `TitleKey` carries the whole title, so it shares the `Todo` bound.
+const INDEX_KEY_MAX_SIZE: u32 = MAX_VALUE_SIZE;
+
+#[derive(CandidType, Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct CompletedKey {
+    completed: bool,
+    id: u64,
+}
+
+#[derive(CandidType, Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct TitleKey {
+    title: String,
+    id: u64,
+}
+
+impl Storable for CompletedKey {
+    fn to_bytes(&self) -> std::borrow::Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Bounded {
+        max_size: INDEX_KEY_MAX_SIZE,
+        is_fixed_size: false,
+    };
+}
+
+impl Storable for TitleKey {
+    fn to_bytes(&self) -> std::borrow::Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Bounded {
+        max_size: INDEX_KEY_MAX_SIZE,
+        is_fixed_size: false,
+    };
+}
+
 thread_local! {
      // The memory manager is used for simulating multiple memories. Given a `MemoryId` it can
     // return a memory that can be used by stable structures.
@@ -60,72 +216,444 @@ thread_local! {
         )
     );
     // static TODOS: RefCell<BTreeMap<u64, Todo>> = RefCell::new(BTreeMap::new());
+
+    // Secondary indexes, each in its own memory so they never alias `TODOS`.
+    static COMPLETED_IDX: RefCell<StableBTreeMap<CompletedKey, (), Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(1))),
+        )
+    );
+
+    static TITLE_IDX: RefCell<StableBTreeMap<TitleKey, (), Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(2))),
+        )
+    );
+
+    // Last id ever issued. Only ever increments, so ids are globally unique and
+    // never reused even after deletions shrink the table.
+    static ID_COUNTER: RefCell<StableCell<u64, Memory>> = RefCell::new(
+        StableCell::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(3))),
+            0,
+        )
+        .expect("failed to init id counter")
+    );
+
+    // Running count of completed todos, maintained incrementally so `stats`
+    // never has to scan the table.
+    static COMPLETED_COUNT: RefCell<StableCell<u64, Memory>> = RefCell::new(
+        StableCell::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(4))),
+            0,
+        )
+        .expect("failed to init completed counter")
+    );
+
+    // Deployment-tunable configuration.
+    static CONFIG: RefCell<StableCell<Config, Memory>> = RefCell::new(
+        StableCell::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(5))),
+            Config::default(),
+        )
+        .expect("failed to init config")
+    );
 }
 
 // ------------------------------------
-// CRUD Functions
+// Counters
 // ------------------------------------
-#[ic_cdk::update]
-fn create_todo(title: String) -> u64 {
-    let created_id = TODOS.with(|todos| {
-        let mut map = todos.borrow_mut();
-        let id = map.len() as u64 + 1;
-        map.insert(
-            id,
-            Todo {
-                id,
-                title,
-                completed: false,
+// Allocate the next monotonic id, persisting the new high-water mark.
+fn next_id() -> u64 {
+    ID_COUNTER.with(|counter| {
+        let mut cell = counter.borrow_mut();
+        let next = *cell.get() + 1;
+        cell.set(next).expect("failed to set id counter");
+        next
+    })
+}
+
+// How the completed count changes when a todo's `completed` flag moves.
+fn completed_delta(old: bool, new: bool) -> i64 {
+    (new as i64) - (old as i64)
+}
+
+// Apply a signed delta to the persistent completed-todo counter.
+fn bump_completed(delta: i64) {
+    if delta == 0 {
+        return;
+    }
+    COMPLETED_COUNT.with(|counter| {
+        let mut cell = counter.borrow_mut();
+        let updated = (*cell.get() as i64 + delta).max(0) as u64;
+        cell.set(updated).expect("failed to set completed counter");
+    });
+}
+
+// Aggregate storage statistics, all O(1) to read.
+#[derive(CandidType, Deserialize, Debug, Clone)]
+struct Stats {
+    total_todos: u64,
+    completed_todos: u64,
+    next_id: u64,
+}
+
+// ------------------------------------
+// Index maintenance
+// ------------------------------------
+// Insert/remove the composite keys for a todo. Callers must keep these in step
+// with every mutation of `TODOS` inside the same update so the indexes can
+// never drift from the primary store.
+fn index_insert(todo: &Todo) {
+    COMPLETED_IDX.with(|idx| {
+        idx.borrow_mut().insert(
+            CompletedKey {
+                completed: todo.completed,
+                id: todo.id,
+            },
+            (),
+        );
+    });
+    TITLE_IDX.with(|idx| {
+        idx.borrow_mut().insert(
+            TitleKey {
+                title: todo.title.clone(),
+                id: todo.id,
             },
+            (),
         );
-        println!("Created id: {}", id);
-        id
     });
-    created_id
+}
+
+fn index_remove(todo: &Todo) {
+    COMPLETED_IDX.with(|idx| {
+        idx.borrow_mut().remove(&CompletedKey {
+            completed: todo.completed,
+            id: todo.id,
+        });
+    });
+    TITLE_IDX.with(|idx| {
+        idx.borrow_mut().remove(&TitleKey {
+            title: todo.title.clone(),
+            id: todo.id,
+        });
+    });
+}
+
+// Load the full `Todo` records for a page of ids gathered from an index scan.
+fn load_page(ids: Vec<u64>, limit: usize) -> TodosPage {
+    let has_next_page = ids.len() > limit;
+    let page_ids = if has_next_page { &ids[..limit] } else { &ids[..] };
+    let todos: Vec<Todo> = TODOS.with(|todos| {
+        let map = todos.borrow();
+        page_ids.iter().filter_map(|id| map.get(id)).collect()
+    });
+    let cursor = todos.last().map(|todo| todo.id);
+    TodosPage {
+        todos,
+        page_info: PageInfo {
+            cursor,
+            has_next_page,
+        },
+    }
+}
+
+// ------------------------------------
+// CRUD Functions
+// ------------------------------------
+#[ic_cdk::update]
+fn create_todo(title: String) -> Result<u64, TodoError> {
+    validate_title(&title)?;
+    let id = next_id();
+    let todo = Todo {
+        id,
+        title,
+        completed: false,
+    };
+    TODOS.with(|todos| todos.borrow_mut().insert(id, todo.clone()));
+    index_insert(&todo);
+    println!("Created id: {}", id);
+    Ok(id)
 }
 
 #[ic_cdk::query]
-fn read_todos(page_num: usize, page_size: usize) -> Option<Vec<Todo>> {
+fn read_todos(after: Option<u64>, first: Option<u32>) -> Result<TodosPage, TodoError> {
+    let limit = resolve_page_size(first)?;
+
     let page = TODOS.with(|todos| {
         let map = todos.borrow();
-        map.get_page(page_num, page_size)
+        // Start strictly after the supplied cursor so the walk stays logarithmic
+        // instead of skipping over every preceding entry.
+        let start = match after {
+            Some(cursor) => std::ops::Bound::Excluded(cursor),
+            None => std::ops::Bound::Unbounded,
+        };
+        // Fetch one extra item: its presence is what tells us a next page exists.
+        map.range((start, std::ops::Bound::Unbounded))
+            .take(limit + 1)
+            .collect::<Vec<(u64, Todo)>>()
     });
 
-    if page.is_empty() {
-        println!("No todos found on page {}", page_num);
-        None
-    } else {
-        Some(page.into_iter().map(|(_, todo)| todo).collect())
-        // println!("--- Page {} ---", page_num);
-        // for (id, todo) in page {
-        //     println!("{}: {} (Completed: {})", id, todo.title, todo.completed);
-        // }
+    let has_next_page = page.len() > limit;
+    let mut todos: Vec<Todo> = page.into_iter().map(|(_, todo)| todo).collect();
+    if has_next_page {
+        todos.truncate(limit);
     }
+    let cursor = todos.last().map(|todo| todo.id);
+
+    Ok(TodosPage {
+        todos,
+        page_info: PageInfo {
+            cursor,
+            has_next_page,
+        },
+    })
 }
 
 #[ic_cdk::update]
-fn update_todo(id: u64, title: Option<String>, completed: Option<bool>) {
-    TODOS.with(|todos| {
+fn update_todo(
+    id: u64,
+    title: Option<String>,
+    completed: Option<bool>,
+) -> Result<(), TodoError> {
+    if let Some(new_title) = &title {
+        validate_title(new_title)?;
+    }
+    let delta = TODOS.with(|todos| -> Result<i64, TodoError> {
         let mut todos_mut = todos.borrow_mut();
-        let mut mutable_todo = todos_mut.get(&id).unwrap();
-        let mutable_todo_upd = {
-            if let Some(new_title) = title {
-                mutable_todo.title = new_title;
-            }
-            if let Some(new_completed) = completed {
-                mutable_todo.completed = new_completed;
-            }
-            mutable_todo
-        };
-        todos_mut.insert(id, mutable_todo_upd);
-    })
+        let old_todo = todos_mut.get(&id).ok_or(TodoError::NotFound { id })?;
+        let mut mutable_todo = old_todo.clone();
+        if let Some(new_title) = title {
+            mutable_todo.title = new_title;
+        }
+        if let Some(new_completed) = completed {
+            mutable_todo.completed = new_completed;
+        }
+        // Retire the old composite keys and publish the new ones in the same
+        // update so a changed `title`/`completed` can't leave a stale index row.
+        index_remove(&old_todo);
+        todos_mut.insert(id, mutable_todo.clone());
+        index_insert(&mutable_todo);
+        Ok(completed_delta(old_todo.completed, mutable_todo.completed))
+    })?;
+    bump_completed(delta);
+    Ok(())
 }
 
 #[ic_cdk::update]
 fn delete_todo(id: u64) {
-    TODOS.with(|todos| {
-        todos.borrow_mut().remove(&id);
-    })
+    let removed = TODOS.with(|todos| todos.borrow_mut().remove(&id));
+    if let Some(todo) = removed {
+        index_remove(&todo);
+        if todo.completed {
+            bump_completed(-1);
+        }
+    }
+}
+
+#[ic_cdk::query]
+fn stats() -> Stats {
+    Stats {
+        total_todos: TODOS.with(|todos| todos.borrow().len()),
+        completed_todos: COMPLETED_COUNT.with(|counter| *counter.borrow().get()),
+        next_id: ID_COUNTER.with(|counter| *counter.borrow().get()) + 1,
+    }
+}
+
+#[ic_cdk::query]
+fn get_config() -> Config {
+    config()
+}
+
+// Tune the maximum accepted title length. Rejected if it would let a record
+// outgrow `MAX_VALUE_SIZE`, so the declared `Bound` can never be exceeded.
+#[ic_cdk::update]
+fn set_max_title_bytes(max_title_bytes: u32) -> Result<(), TodoError> {
+    if max_title_bytes > MAX_TITLE_BYTES_CEILING {
+        return Err(TodoError::ConfigOutOfRange {
+            requested: max_title_bytes,
+            ceiling: MAX_TITLE_BYTES_CEILING,
+        });
+    }
+    CONFIG.with(|cfg| {
+        let mut cell = cfg.borrow_mut();
+        let mut updated = cell.get().clone();
+        updated.max_title_bytes = max_title_bytes;
+        cell.set(updated).expect("failed to set config");
+    });
+    Ok(())
+}
+
+// ------------------------------------
+// Index-backed queries
+// ------------------------------------
+#[ic_cdk::query]
+fn list_by_completed(
+    completed: bool,
+    after: Option<u64>,
+    first: Option<u32>,
+) -> Result<TodosPage, TodoError> {
+    let limit = resolve_page_size(first)?;
+    let start = match after {
+        Some(cursor) => std::ops::Bound::Excluded(CompletedKey {
+            completed,
+            id: cursor,
+        }),
+        None => std::ops::Bound::Included(CompletedKey { completed, id: 0 }),
+    };
+    let end = std::ops::Bound::Included(CompletedKey {
+        completed,
+        id: u64::MAX,
+    });
+    let ids = COMPLETED_IDX.with(|idx| {
+        idx.borrow()
+            .range((start, end))
+            .take(limit + 1)
+            .map(|(key, _)| key.id)
+            .collect::<Vec<u64>>()
+    });
+    Ok(load_page(ids, limit))
+}
+
+#[ic_cdk::query]
+fn list_by_title_prefix(
+    prefix: String,
+    after: Option<u64>,
+    first: Option<u32>,
+) -> Result<TodosPage, TodoError> {
+    let limit = resolve_page_size(first)?;
+    // Resume precisely from the cursor by rebuilding its full `(title, id)`
+    // key; if the cursor todo is gone, fall back to the prefix lower bound.
+    let start = match after.and_then(|cursor| {
+        TODOS.with(|todos| todos.borrow().get(&cursor).map(|todo| (todo.title, cursor)))
+    }) {
+        Some((title, id)) => std::ops::Bound::Excluded(TitleKey { title, id }),
+        None => std::ops::Bound::Included(TitleKey {
+            title: prefix.clone(),
+            id: 0,
+        }),
+    };
+    let ids = TITLE_IDX.with(|idx| {
+        idx.borrow()
+            .range((start, std::ops::Bound::Unbounded))
+            .take_while(|(key, _)| key.title.starts_with(&prefix))
+            .take(limit + 1)
+            .map(|(key, _)| key.id)
+            .collect::<Vec<u64>>()
+    });
+    Ok(load_page(ids, limit))
+}
+
+// ------------------------------------
+// Transactional batches
+// ------------------------------------
+// A single mutation within a batch.
+#[derive(CandidType, Deserialize, Debug, Clone)]
+enum TodoOp {
+    Create {
+        title: String,
+    },
+    Update {
+        id: u64,
+        title: Option<String>,
+        completed: Option<bool>,
+    },
+    Delete {
+        id: u64,
+    },
+}
+
+// Resolve the current state of `id`, preferring the pending overlay over the
+// committed store so ops within a batch observe each other.
+fn overlay_get(overlay: &BTreeMap<u64, Option<Todo>>, id: u64) -> Option<Todo> {
+    match overlay.get(&id) {
+        Some(slot) => slot.clone(),
+        None => TODOS.with(|todos| todos.borrow().get(&id)),
+    }
+}
+
+// Apply a group of ops atomically: every op is validated against an in-memory
+// overlay first, and the overlay is only flushed to `TODOS` (and the secondary
+// indexes) once all ops pass. A single failing op returns an `Err` describing
+// it and leaves the store — and every index — completely untouched.
+#[ic_cdk::update]
+fn apply_batch(ops: Vec<TodoOp>) -> Result<Vec<u64>, TodoError> {
+    let mut overlay: BTreeMap<u64, Option<Todo>> = BTreeMap::new();
+    let mut created: Vec<u64> = Vec::new();
+    // Provisional high-water mark; only flushed to `ID_COUNTER` on commit so a
+    // rejected batch consumes no ids.
+    let mut cursor = ID_COUNTER.with(|counter| *counter.borrow().get());
+
+    // Validation pass — stage everything, commit nothing.
+    for op in ops {
+        match op {
+            TodoOp::Create { title } => {
+                validate_title(&title)?;
+                cursor += 1;
+                let id = cursor;
+                overlay.insert(
+                    id,
+                    Some(Todo {
+                        id,
+                        title,
+                        completed: false,
+                    }),
+                );
+                created.push(id);
+            }
+            TodoOp::Update {
+                id,
+                title,
+                completed,
+            } => {
+                let mut todo = overlay_get(&overlay, id).ok_or(TodoError::NotFound { id })?;
+                if let Some(new_title) = title {
+                    validate_title(&new_title)?;
+                    todo.title = new_title;
+                }
+                if let Some(new_completed) = completed {
+                    todo.completed = new_completed;
+                }
+                overlay.insert(id, Some(todo));
+            }
+            TodoOp::Delete { id } => {
+                overlay_get(&overlay, id).ok_or(TodoError::NotFound { id })?;
+                overlay.insert(id, None);
+            }
+        }
+    }
+
+    // Commit pass — every op validated, so flush the overlay and keep the
+    // indexes and counters in lockstep with each primary-store change.
+    let mut delta: i64 = 0;
+    for (id, slot) in overlay {
+        let previous = TODOS.with(|todos| todos.borrow().get(&id));
+        let was_completed = previous.as_ref().map(|t| t.completed).unwrap_or(false);
+        if let Some(old) = &previous {
+            index_remove(old);
+        }
+        match slot {
+            Some(todo) => {
+                delta += completed_delta(was_completed, todo.completed);
+                TODOS.with(|todos| todos.borrow_mut().insert(id, todo.clone()));
+                index_insert(&todo);
+            }
+            None => {
+                delta += completed_delta(was_completed, false);
+                TODOS.with(|todos| todos.borrow_mut().remove(&id));
+            }
+        }
+    }
+    bump_completed(delta);
+    // Persist any ids consumed by `Create` ops.
+    ID_COUNTER.with(|counter| {
+        let mut cell = counter.borrow_mut();
+        if cursor > *cell.get() {
+            cell.set(cursor).expect("failed to set id counter");
+        }
+    });
+
+    Ok(created)
 }
 
 // ----------------------------
@@ -140,7 +668,7 @@ mod tests {
 
     #[test]
     fn test_create_todo() {
-        create_todo("Test todo".to_string());
+        create_todo("Test todo".to_string()).unwrap();
         assert_eq!(TODOS.with(|todos| todos.borrow().len()), 1);
         let title = TODOS.with(|todos| {
             let map = todos.borrow();
@@ -153,36 +681,171 @@ mod tests {
     #[test]
     fn test_read_todos_1() {
         for i in 1..=100 {
-            create_todo(format!("Task {}", i));
+            create_todo(format!("Task {}", i)).unwrap();
         }
-        let page_size = 10;
-        let num_pages = (100 + page_size - 1) / page_size;
-
-        // num_pages + 1 : for covering the case when page_num exceeds the bounds.
-        for page_num in 1..=num_pages + 1 {
-            if let Some(page_todos) = read_todos(page_num, page_size) {
-                assert_eq!(
-                    page_todos.len(),
-                    if page_num > num_pages {
-                        100 % page_size
-                    } else {
-                        page_size
-                    }
-                );
+        let page_size: u32 = 10;
 
-                for (idx, todo) in page_todos.iter().enumerate() {
-                    let expected_title = format!("Task {}", (page_num - 1) * page_size + idx + 1);
-                    assert_eq!(todo.title, expected_title);
-                }
+        // Walk the whole list by following the cursor page by page.
+        let mut after: Option<u64> = None;
+        let mut seen = 0usize;
+        loop {
+            let page = read_todos(after, Some(page_size)).unwrap();
+            for (idx, todo) in page.todos.iter().enumerate() {
+                let expected_title = format!("Task {}", seen + idx + 1);
+                assert_eq!(todo.title, expected_title);
+            }
+            seen += page.todos.len();
+            if !page.page_info.has_next_page {
+                break;
             }
+            assert_eq!(page.todos.len(), page_size as usize);
+            after = page.page_info.cursor;
         }
+        assert_eq!(seen, 100);
+    }
+
+    #[test]
+    fn test_read_todos_rejects_oversized_page() {
+        assert_eq!(
+            read_todos(None, Some(DEFAULT_MAX_PAGE_SIZE + 1)).unwrap_err(),
+            TodoError::PageSizeTooLarge {
+                requested: DEFAULT_MAX_PAGE_SIZE + 1,
+                max: DEFAULT_MAX_PAGE_SIZE,
+            }
+        );
+    }
+
+    #[test]
+    fn test_list_by_completed() {
+        let a = create_todo("alpha".to_string()).unwrap();
+        let _b = create_todo("beta".to_string()).unwrap();
+        let c = create_todo("gamma".to_string()).unwrap();
+        update_todo(a, None, Some(true)).unwrap();
+        update_todo(c, None, Some(true)).unwrap();
+
+        let done = list_by_completed(true, None, None).unwrap();
+        let ids: Vec<u64> = done.todos.iter().map(|t| t.id).collect();
+        assert_eq!(ids, vec![a, c]);
+
+        let pending = list_by_completed(false, None, None).unwrap();
+        assert_eq!(pending.todos.len(), 1);
+    }
+
+    #[test]
+    fn test_list_by_title_prefix() {
+        create_todo("apple".to_string()).unwrap();
+        create_todo("apricot".to_string()).unwrap();
+        create_todo("banana".to_string()).unwrap();
+
+        let page = list_by_title_prefix("ap".to_string(), None, None).unwrap();
+        let titles: Vec<String> = page.todos.iter().map(|t| t.title.clone()).collect();
+        assert_eq!(titles, vec!["apple".to_string(), "apricot".to_string()]);
+    }
+
+    #[test]
+    fn test_apply_batch_commits_all() {
+        let ids = apply_batch(vec![
+            TodoOp::Create {
+                title: "one".to_string(),
+            },
+            TodoOp::Create {
+                title: "two".to_string(),
+            },
+        ])
+        .unwrap();
+        assert_eq!(ids.len(), 2);
+        let second = apply_batch(vec![TodoOp::Update {
+            id: ids[0],
+            title: Some("one-edited".to_string()),
+            completed: Some(true),
+        }])
+        .unwrap();
+        assert!(second.is_empty());
+        let todo = TODOS.with(|todos| todos.borrow().get(&ids[0]).unwrap());
+        assert_eq!(todo.title, "one-edited");
+        assert!(todo.completed);
+    }
+
+    #[test]
+    fn test_apply_batch_rolls_back_on_failure() {
+        let before = TODOS.with(|todos| todos.borrow().len());
+        let result = apply_batch(vec![
+            TodoOp::Create {
+                title: "kept?".to_string(),
+            },
+            // References an id that cannot exist -> whole batch must abort.
+            TodoOp::Update {
+                id: u64::MAX,
+                title: Some("nope".to_string()),
+                completed: None,
+            },
+        ]);
+        assert_eq!(result, Err(TodoError::NotFound { id: u64::MAX }));
+        assert_eq!(TODOS.with(|todos| todos.borrow().len()), before);
+    }
+
+    #[test]
+    fn test_stats_and_monotonic_ids() {
+        let a = create_todo("a".to_string()).unwrap();
+        let b = create_todo("b".to_string()).unwrap();
+        update_todo(b, None, Some(true)).unwrap();
+
+        let s = stats();
+        assert_eq!(s.total_todos, 2);
+        assert_eq!(s.completed_todos, 1);
+        assert_eq!(s.next_id, b + 1);
+
+        // Deleting and recreating must never reuse an id.
+        delete_todo(a);
+        let c = create_todo("c".to_string()).unwrap();
+        assert!(c > b);
+        let s = stats();
+        assert_eq!(s.total_todos, 2);
+        assert_eq!(s.completed_todos, 1);
+    }
+
+    #[test]
+    fn test_title_bound_boundary() {
+        let max = config().max_title_bytes as usize;
+
+        // Exactly at the limit is accepted and stored without panicking on encode.
+        let at_limit = "x".repeat(max);
+        let id = create_todo(at_limit.clone()).unwrap();
+        let stored = TODOS.with(|todos| todos.borrow().get(&id).unwrap());
+        assert_eq!(stored.title.len(), max);
+
+        // One byte over is rejected with a structured error, never a panic.
+        let over_limit = "x".repeat(max + 1);
+        assert_eq!(
+            create_todo(over_limit),
+            Err(TodoError::TitleTooLong {
+                bytes: max + 1,
+                max,
+            })
+        );
+    }
+
+    #[test]
+    fn test_set_max_title_bytes() {
+        assert_eq!(
+            set_max_title_bytes(MAX_TITLE_BYTES_CEILING + 1),
+            Err(TodoError::ConfigOutOfRange {
+                requested: MAX_TITLE_BYTES_CEILING + 1,
+                ceiling: MAX_TITLE_BYTES_CEILING,
+            })
+        );
+
+        set_max_title_bytes(8).unwrap();
+        assert_eq!(config().max_title_bytes, 8);
+        assert!(create_todo("too-long-title".to_string()).is_err());
+        assert!(create_todo("short".to_string()).is_ok());
     }
 
     #[test]
     fn test_update_todo() {
-        let todo_id = create_todo("Test todo".to_string());
+        let todo_id = create_todo("Test todo".to_string()).unwrap();
 
-        update_todo(todo_id, Some("Updated title".to_string()), Some(true));
+        update_todo(todo_id, Some("Updated title".to_string()), Some(true)).unwrap();
 
         let updated_todo = TODOS.with(|todos| todos.borrow().get(&todo_id).unwrap().clone());
         dbg!(updated_todo.clone());
@@ -192,7 +855,7 @@ mod tests {
 
     #[test]
     fn test_delete_todo() {
-        create_todo("Test todo".to_string());
+        create_todo("Test todo".to_string()).unwrap();
         let mut todo_id = 0;
         TODOS.with(|todos| {
             let map = todos.borrow();